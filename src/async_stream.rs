@@ -0,0 +1,130 @@
+//! An async analogue of [`crate::stream::DataLogReader`] for sources that hand over bytes via
+//! [`tokio::io::AsyncRead`] (a socket, a growing file, ...) instead of being pushed them
+//! synchronously.
+//!
+//! A yielded [`Record`] borrows from the reader's internal buffer, the same way the sync
+//! reader's `next_record` does, which is incompatible with `futures_core::Stream` (its `Item`
+//! can't borrow from `&mut self` across `poll_next` calls). Pull records in a loop instead:
+//!
+//! ```ignore
+//! let mut reader = AsyncDataLogReader::new(socket);
+//! while let Some(record) = reader.next_record().await? {
+//!     // ...
+//! }
+//! ```
+
+#![cfg(feature = "tokio")]
+
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{stream::DataLogReader, Record};
+
+/// The size of each read off the underlying [`AsyncRead`] source.
+const READ_CHUNK_SIZE: usize = 4096;
+
+#[derive(Debug, Error)]
+pub enum AsyncStreamError {
+    #[error("Failed to read from the underlying source: {0}")]
+    Io(#[from] std::io::Error),
+    /// A malformed log, as reported by [`DataLogReader::next_record`]. Stored as a string
+    /// since the originating [`crate::ParsingError`] borrows from a buffer this error may
+    /// outlive.
+    #[error("Malformed DataLog: {0}")]
+    Parse(String),
+}
+
+/// Wraps any [`AsyncRead`] source, buffering incoming bytes and handing back whole records as
+/// they become available.
+pub struct AsyncDataLogReader<R> {
+    inner: R,
+    reader: DataLogReader,
+    read_buf: [u8; READ_CHUNK_SIZE],
+}
+
+impl<R: AsyncRead + Unpin> AsyncDataLogReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            reader: DataLogReader::new(),
+            read_buf: [0; READ_CHUNK_SIZE],
+        }
+    }
+
+    /// The log's extra-header string, once the header has been read off the source.
+    pub fn metadata(&self) -> Option<&str> {
+        self.reader.metadata()
+    }
+
+    /// Reads off `self`'s source until the next record is fully buffered, then returns it.
+    ///
+    /// Returns `Ok(None)` once the source is exhausted, or `Err` for a malformed log. A partial
+    /// record still buffered when the source is exhausted is not reported: that case also
+    /// returns `Ok(None)`. This is the same incomplete-vs-malformed split as
+    /// [`DataLogReader::next_record`], just awaiting more bytes instead of requiring the
+    /// caller to `push` them.
+    pub async fn next_record(&mut self) -> Result<Option<Record<'_>>, AsyncStreamError> {
+        loop {
+            if let Some(record) = self
+                .reader
+                .next_record()
+                .map_err(|e| AsyncStreamError::Parse(e.to_string()))?
+            {
+                return Ok(Some(record));
+            }
+
+            let n = self.inner.read(&mut self.read_buf).await?;
+            if n == 0 {
+                return Ok(None);
+            }
+
+            self.reader.push(&self.read_buf[..n]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::RecordPayload;
+
+    const HEADER: [u8; 0x10] = [
+        0x57, 0x50, 0x49, 0x4C, 0x4F, 0x47, // Magic = "WPILOG"
+        0x00, 0x01, // Version = 0x0100
+        0x04, 0x00, 0x00, 0x00, // Metadata Length = 4
+        0x74, 0x65, 0x73, 0x74, // Metadata = "test"
+    ];
+
+    const INT_RECORD: [u8; 0x0E] = [
+        0x20, // Timestamp Length = 3, Payload Size Length = 1, Entry Id Length = 1
+        0x01, // Entry Id = 1
+        0x08, // Payload Size = 8
+        0x40, 0x42, 0x0F, // Timestamp = 1_000_000
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Payload = [u8; 8]
+    ];
+
+    #[tokio::test]
+    async fn test_next_record_yields_every_buffered_record_in_order() {
+        let mut bytes = HEADER.to_vec();
+        bytes.extend_from_slice(&INT_RECORD);
+        bytes.extend_from_slice(&INT_RECORD);
+        bytes.extend_from_slice(&INT_RECORD);
+
+        let mut reader = AsyncDataLogReader::new(Cursor::new(bytes));
+
+        for _ in 0..3 {
+            let record = reader
+                .next_record()
+                .await
+                .expect("should parse")
+                .expect("should be fully buffered");
+
+            assert_eq!(record.entry_id, 1);
+            assert_eq!(record.payload, RecordPayload::Value(&3i64.to_le_bytes()));
+        }
+
+        assert!(reader.next_record().await.expect("should parse").is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! A typed ("cooked") view over the raw bytes returned by [`Entry::get_value`](crate::read_only::Entry::get_value).
+//!
+//! WPILOG only tells you the declared type string for an entry (e.g. `"int64"`); every
+//! caller is otherwise left to decode the raw payload by hand. This module interprets
+//! that payload according to the entry's type, following the standard WPILOG encodings.
+
+use core::mem::size_of;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    errors::WPILogParseError,
+    read_only::{Entry, Timestamp},
+};
+
+/// A decoded WPILOG value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Boolean(bool),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    /// Used for both the `"string"` and `"json"` WPILOG types.
+    String(&'a str),
+    BooleanArray(Vec<bool>),
+    Int64Array(Vec<i64>),
+    FloatArray(Vec<f32>),
+    DoubleArray(Vec<f64>),
+    StringArray(Vec<&'a str>),
+    /// Payload for an entry type this module doesn't know how to decode.
+    Raw(&'a [u8]),
+}
+
+fn expect_len(ty: &str, payload: &[u8], len: usize) -> Result<(), WPILogParseError> {
+    if payload.len() != len {
+        return Err(WPILogParseError::InvalidValuePayload {
+            ty: ty.to_string(),
+            expected: len,
+            got: payload.len(),
+        });
+    }
+
+    Ok(())
+}
+
+fn decode_array<'a, T>(
+    ty: &str,
+    payload: &'a [u8],
+    element_size: usize,
+    decode: impl Fn(&'a [u8]) -> T,
+) -> Result<Vec<T>, WPILogParseError> {
+    if payload.len() % element_size != 0 {
+        return Err(WPILogParseError::InvalidValuePayload {
+            ty: ty.to_string(),
+            expected: payload.len() - (payload.len() % element_size),
+            got: payload.len(),
+        });
+    }
+
+    Ok(payload.chunks_exact(element_size).map(decode).collect())
+}
+
+fn decode_string_array<'a>(payload: &'a [u8]) -> Result<Vec<&'a str>, WPILogParseError> {
+    const U32_SIZE: usize = size_of::<u32>();
+
+    if payload.len() < U32_SIZE {
+        return Err(WPILogParseError::InvalidValuePayload {
+            ty: "string[]".to_string(),
+            expected: U32_SIZE,
+            got: payload.len(),
+        });
+    }
+
+    let count = u32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]) as usize;
+    let mut rest = &payload[U32_SIZE..];
+    let mut strings = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        if rest.len() < U32_SIZE {
+            return Err(WPILogParseError::InvalidValuePayload {
+                ty: "string[]".to_string(),
+                expected: U32_SIZE,
+                got: rest.len(),
+            });
+        }
+
+        let len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        rest = &rest[U32_SIZE..];
+
+        if rest.len() < len {
+            return Err(WPILogParseError::InvalidValuePayload {
+                ty: "string[]".to_string(),
+                expected: len,
+                got: rest.len(),
+            });
+        }
+
+        let (s, remainder) = rest.split_at(len);
+        strings.push(core::str::from_utf8(s).map_err(WPILogParseError::Utf8Error)?);
+        rest = remainder;
+    }
+
+    Ok(strings)
+}
+
+/// Decodes a raw value payload according to its declared WPILOG type string.
+///
+/// Unknown type strings fall through to [`Value::Raw`] rather than erroring, since the
+/// payload may still be meaningful to a caller that knows the type out-of-band.
+pub fn decode<'a>(ty: &str, payload: &'a [u8]) -> Result<Value<'a>, WPILogParseError> {
+    Ok(match ty {
+        "boolean" => {
+            expect_len(ty, payload, 1)?;
+            Value::Boolean(payload[0] != 0)
+        }
+        "int64" => {
+            expect_len(ty, payload, 8)?;
+            Value::Int64(i64::from_le_bytes(payload.try_into().unwrap()))
+        }
+        "float" => {
+            expect_len(ty, payload, 4)?;
+            Value::Float(f32::from_le_bytes(payload.try_into().unwrap()))
+        }
+        "double" => {
+            expect_len(ty, payload, 8)?;
+            Value::Double(f64::from_le_bytes(payload.try_into().unwrap()))
+        }
+        "string" | "json" => {
+            Value::String(core::str::from_utf8(payload).map_err(WPILogParseError::Utf8Error)?)
+        }
+        "boolean[]" => Value::BooleanArray(decode_array(ty, payload, 1, |b| b[0] != 0)?),
+        "int64[]" => Value::Int64Array(decode_array(ty, payload, 8, |b| {
+            i64::from_le_bytes(b.try_into().unwrap())
+        })?),
+        "float[]" => Value::FloatArray(decode_array(ty, payload, 4, |b| {
+            f32::from_le_bytes(b.try_into().unwrap())
+        })?),
+        "double[]" => Value::DoubleArray(decode_array(ty, payload, 8, |b| {
+            f64::from_le_bytes(b.try_into().unwrap())
+        })?),
+        "string[]" => Value::StringArray(decode_string_array(payload)?),
+        _ => Value::Raw(payload),
+    })
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the value at `idx`, decoded according to this entry's declared type.
+    ///
+    /// See [`decode`] for the supported WPILOG type strings; an unrecognized type
+    /// yields [`Value::Raw`] rather than an error.
+    pub fn get_typed_value(&self, idx: usize) -> Option<Result<(Timestamp, Value<'a>), WPILogParseError>> {
+        let (timestamp, payload) = self.get_value(idx)?;
+
+        Some(decode(self.ty(), payload).map(|value| (*timestamp, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_scalars() {
+        assert_eq!(decode("boolean", &[1]).unwrap(), Value::Boolean(true));
+        assert_eq!(decode("int64", &3i64.to_le_bytes()).unwrap(), Value::Int64(3));
+        assert_eq!(decode("float", &1.5f32.to_le_bytes()).unwrap(), Value::Float(1.5));
+        assert_eq!(decode("double", &2.5f64.to_le_bytes()).unwrap(), Value::Double(2.5));
+        assert_eq!(decode("string", b"hi").unwrap(), Value::String("hi"));
+        assert_eq!(decode("json", b"{}").unwrap(), Value::String("{}"));
+    }
+
+    #[test]
+    fn test_decode_arrays() {
+        assert_eq!(
+            decode("boolean[]", &[1, 0, 1]).unwrap(),
+            Value::BooleanArray(vec![true, false, true])
+        );
+        let mut int64_array_payload = 1i64.to_le_bytes().to_vec();
+        int64_array_payload.extend_from_slice(&2i64.to_le_bytes());
+        assert_eq!(
+            decode("int64[]", &int64_array_payload).unwrap(),
+            Value::Int64Array(vec![1, 2])
+        );
+
+        let mut string_array_payload = 2u32.to_le_bytes().to_vec();
+        string_array_payload.extend_from_slice(&3u32.to_le_bytes());
+        string_array_payload.extend_from_slice(b"foo");
+        string_array_payload.extend_from_slice(&3u32.to_le_bytes());
+        string_array_payload.extend_from_slice(b"bar");
+
+        assert_eq!(
+            decode("string[]", &string_array_payload).unwrap(),
+            Value::StringArray(vec!["foo", "bar"])
+        );
+    }
+
+    #[test]
+    fn test_decode_wrong_length_errors() {
+        let err = decode("int64", &[0u8; 4]).unwrap_err();
+        assert!(matches!(
+            err,
+            WPILogParseError::InvalidValuePayload { ref ty, expected: 8, got: 4 } if ty == "int64"
+        ));
+    }
+
+    #[test]
+    fn test_decode_unknown_type_falls_through_to_raw() {
+        assert_eq!(decode("custom", &[1, 2, 3]).unwrap(), Value::Raw(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_get_typed_value_via_parse() {
+        // `Entry` has no public constructor, so build one the only way a caller can: parsing
+        // a real .wpilog buffer.
+        let mut bytes = vec![];
+        bytes.extend_from_slice(crate::WPILOG_MAGIC);
+        bytes.extend_from_slice(&crate::SUPPORTED_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut start_payload = vec![0u8, 1, 0, 0, 0];
+        start_payload.extend_from_slice(&8u32.to_le_bytes());
+        start_payload.extend_from_slice(b"/Counter");
+        start_payload.extend_from_slice(&5u32.to_le_bytes());
+        start_payload.extend_from_slice(b"int64");
+        start_payload.extend_from_slice(&0u32.to_le_bytes());
+        bytes.push(0b0010_0000);
+        bytes.push(0);
+        bytes.push(start_payload.len() as u8);
+        bytes.extend_from_slice(&1_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&start_payload);
+
+        bytes.push(0b0010_0000);
+        bytes.push(1);
+        bytes.push(8);
+        bytes.extend_from_slice(&1_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&3i64.to_le_bytes());
+
+        let log = crate::read_only::WPILog::parse(&bytes).expect("should parse");
+        let entry = log.get("/Counter").expect("entry should exist");
+
+        let (timestamp, value) = entry.get_typed_value(0).unwrap().unwrap();
+        assert_eq!(timestamp, 1_000);
+        assert_eq!(value, Value::Int64(3));
+
+        assert!(entry.get_typed_value(1).is_none());
+    }
+}
@@ -0,0 +1,189 @@
+//! A typed decoding pass over a parsed [`DataLog`].
+//!
+//! `RecordPayload::Value` only hands back an opaque `&[u8]`, even though the preceding
+//! `Start` record already declared the entry's [`EntryType`]. This module replays a log's
+//! records in order, tracking which [`EntryType`] is currently bound to each entry id, and
+//! decodes every value payload according to it.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{DataLog, EntryType, RecordPayload};
+
+/// A value decoded according to its entry's declared [`EntryType`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedValue<'a> {
+    Boolean(bool),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    String(&'a str),
+    Raw(&'a [u8]),
+    Array(Vec<DecodedValue<'a>>),
+}
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum DecodeError {
+    #[error("Value record referenced entry id {0} with no currently-open Start")]
+    UnknownEntryId(u32),
+    #[error("\"{0}[]\" payload ({1} bytes) does not split into whole elements")]
+    MisalignedArray(&'static str, usize),
+    #[error("\"{ty}\" payload was {got} bytes, expected {expected}")]
+    InvalidPayloadLength {
+        ty: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    #[error("Invalid utf8 in a string payload: {0}")]
+    Utf8Error(std::str::Utf8Error),
+}
+
+/// Decodes every `Value` record in `log`, returning `(timestamp, entry_id, value)` triples in
+/// record order.
+///
+/// WPILOG reuses entry ids after a `Finish`, so the registry this builds always reflects the
+/// most recently seen `Start` for a given id: a later `Start` silently overrides an earlier
+/// one, and a `Value` whose id has no live `Start` is reported as [`DecodeError::UnknownEntryId`]
+/// rather than panicking.
+pub fn decode_values<'a>(
+    log: &DataLog<'a>,
+) -> Result<Vec<(u64, u32, DecodedValue<'a>)>, DecodeError> {
+    let mut registry: HashMap<u32, &EntryType<'a>> = HashMap::new();
+    let mut values = Vec::new();
+
+    for record in &log.records {
+        match &record.payload {
+            RecordPayload::Start {
+                target_entry_id, ty, ..
+            } => {
+                registry.insert(*target_entry_id, ty);
+            }
+            RecordPayload::Finish { target_entry_id } => {
+                registry.remove(target_entry_id);
+            }
+            RecordPayload::Value(payload) => {
+                let ty = registry
+                    .get(&record.entry_id)
+                    .ok_or(DecodeError::UnknownEntryId(record.entry_id))?;
+
+                values.push((record.timestamp, record.entry_id, decode_value(ty, payload)?));
+            }
+            RecordPayload::Metadata { .. } => {}
+        }
+    }
+
+    Ok(values)
+}
+
+fn fixed_width_payload<const N: usize>(
+    ty: &'static str,
+    payload: &[u8],
+) -> Result<[u8; N], DecodeError> {
+    payload
+        .try_into()
+        .map_err(|_| DecodeError::InvalidPayloadLength {
+            ty,
+            expected: N,
+            got: payload.len(),
+        })
+}
+
+fn decode_value<'a>(ty: &EntryType<'a>, payload: &'a [u8]) -> Result<DecodedValue<'a>, DecodeError> {
+    Ok(match ty {
+        EntryType::Boolean => {
+            DecodedValue::Boolean(fixed_width_payload::<1>("boolean", payload)?[0] != 0)
+        }
+        EntryType::Int64 => {
+            DecodedValue::Int64(i64::from_le_bytes(fixed_width_payload("int64", payload)?))
+        }
+        EntryType::Float => {
+            DecodedValue::Float(f32::from_le_bytes(fixed_width_payload("float", payload)?))
+        }
+        EntryType::Double => {
+            DecodedValue::Double(f64::from_le_bytes(fixed_width_payload("double", payload)?))
+        }
+        EntryType::String => {
+            DecodedValue::String(std::str::from_utf8(payload).map_err(DecodeError::Utf8Error)?)
+        }
+        EntryType::Raw | EntryType::Unknown(_) => DecodedValue::Raw(payload),
+        EntryType::Array(element) => DecodedValue::Array(decode_array(element, payload)?),
+    })
+}
+
+fn decode_array<'a>(
+    element: &EntryType<'a>,
+    payload: &'a [u8],
+) -> Result<Vec<DecodedValue<'a>>, DecodeError> {
+    if let EntryType::String = element {
+        // `string[]` is the one array type that isn't tight-packed: a u32 element count
+        // followed by that many u32-length-prefixed strings.
+        return decode_string_array(payload);
+    }
+
+    let element_size = match element {
+        EntryType::Boolean => 1,
+        EntryType::Int64 | EntryType::Double => 8,
+        EntryType::Float => 4,
+        // Nested arrays and raw/unknown elements have no fixed width to chunk by.
+        _ => return Ok(vec![DecodedValue::Raw(payload)]),
+    };
+
+    if payload.len() % element_size != 0 {
+        return Err(DecodeError::MisalignedArray(
+            element_name(element),
+            payload.len(),
+        ));
+    }
+
+    payload
+        .chunks_exact(element_size)
+        .map(|chunk| decode_value(element, chunk))
+        .collect()
+}
+
+fn decode_string_array<'a>(payload: &'a [u8]) -> Result<Vec<DecodedValue<'a>>, DecodeError> {
+    const U32_SIZE: usize = std::mem::size_of::<u32>();
+
+    if payload.len() < U32_SIZE {
+        return Err(DecodeError::MisalignedArray("string", payload.len()));
+    }
+
+    let count = u32::from_le_bytes(payload[..U32_SIZE].try_into().unwrap()) as usize;
+    let mut rest = &payload[U32_SIZE..];
+    let mut values = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        if rest.len() < U32_SIZE {
+            return Err(DecodeError::MisalignedArray("string", rest.len()));
+        }
+
+        let len = u32::from_le_bytes(rest[..U32_SIZE].try_into().unwrap()) as usize;
+        rest = &rest[U32_SIZE..];
+
+        if rest.len() < len {
+            return Err(DecodeError::MisalignedArray("string", rest.len()));
+        }
+
+        let (s, remainder) = rest.split_at(len);
+        values.push(DecodedValue::String(
+            std::str::from_utf8(s).map_err(DecodeError::Utf8Error)?,
+        ));
+        rest = remainder;
+    }
+
+    Ok(values)
+}
+
+fn element_name(ty: &EntryType) -> &'static str {
+    match ty {
+        EntryType::Boolean => "boolean",
+        EntryType::Int64 => "int64",
+        EntryType::Float => "float",
+        EntryType::Double => "double",
+        EntryType::String => "string",
+        EntryType::Raw => "raw",
+        EntryType::Array(_) => "array",
+        EntryType::Unknown(_) => "unknown",
+    }
+}
@@ -0,0 +1,348 @@
+//! Encodes a [`DataLog`] (and individual [`Record`]s/[`RecordPayload`]s) back into wire bytes.
+//!
+//! This packs each record's leading bitfield exactly the way [`crate::parse_record`] decodes
+//! it — entry-id length in bits 0-1, payload-size length in bits 2-3, timestamp length in bits
+//! 4-6 — always choosing the minimal byte length for each field, so a parsed-then-re-emitted
+//! log is byte-identical for the canonical (minimal) encoding.
+
+use crate::{EntryType, RecordPayload, SUPPORTED_VERSION, WPILOG_MAGIC};
+
+fn min_bytes_u32(value: u32) -> u8 {
+    match value {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x1_0000..=0xFF_FFFF => 3,
+        _ => 4,
+    }
+}
+
+fn min_bytes_u64(value: u64) -> u8 {
+    match value {
+        0..=0xFFFF_FFFF => min_bytes_u32(value as u32),
+        0x1_0000_0000..=0xFF_FFFF_FFFF => 5,
+        0x100_0000_0000..=0xFFFF_FFFF_FFFF => 6,
+        0x1_0000_0000_0000..=0xFF_FFFF_FFFF_FFFF => 7,
+        _ => 8,
+    }
+}
+
+fn push_len_prefixed_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn entry_type_name(ty: &EntryType) -> String {
+    match ty {
+        EntryType::Raw => "raw".to_string(),
+        EntryType::Boolean => "boolean".to_string(),
+        EntryType::Int64 => "int64".to_string(),
+        EntryType::Float => "float".to_string(),
+        EntryType::Double => "double".to_string(),
+        EntryType::String => "string".to_string(),
+        EntryType::Array(inner) => format!("{}[]", entry_type_name(inner)),
+        EntryType::Unknown(s) => s.to_string(),
+    }
+}
+
+/// Encodes `entry_id`, `timestamp`, and `payload` as a single record, appending the bytes to
+/// `out`.
+pub fn encode_record(out: &mut Vec<u8>, entry_id: u32, timestamp: u64, payload: &[u8]) {
+    let id_len = min_bytes_u32(entry_id);
+    let size_len = min_bytes_u32(payload.len() as u32);
+    let ts_len = min_bytes_u64(timestamp);
+
+    out.push((id_len - 1) | ((size_len - 1) << 2) | ((ts_len - 1) << 4));
+    out.extend_from_slice(&entry_id.to_le_bytes()[..id_len as usize]);
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes()[..size_len as usize]);
+    out.extend_from_slice(&timestamp.to_le_bytes()[..ts_len as usize]);
+    out.extend_from_slice(payload);
+}
+
+/// Encodes a control record (`entry_id` 0) for `target_entry_id` with the given
+/// `control_type` byte and type-specific `body`.
+fn encode_control_record(
+    out: &mut Vec<u8>,
+    timestamp: u64,
+    control_type: u8,
+    target_entry_id: u32,
+    body: &[u8],
+) {
+    let mut payload = Vec::with_capacity(1 + 4 + body.len());
+    payload.push(control_type);
+    payload.extend_from_slice(&target_entry_id.to_le_bytes());
+    payload.extend_from_slice(body);
+
+    encode_record(out, 0, timestamp, &payload);
+}
+
+/// Encodes the `WPILOG` magic, version, and length-prefixed extra-header string.
+pub fn encode_header(out: &mut Vec<u8>, metadata: &str) {
+    out.extend_from_slice(WPILOG_MAGIC);
+    out.extend_from_slice(&SUPPORTED_VERSION.to_le_bytes());
+    push_len_prefixed_string(out, metadata);
+}
+
+fn encode_payload(out: &mut Vec<u8>, timestamp: u64, entry_id: u32, payload: &RecordPayload) {
+    match payload {
+        RecordPayload::Start {
+            target_entry_id,
+            name,
+            ty,
+            metadata,
+        } => {
+            let mut body = vec![];
+            push_len_prefixed_string(&mut body, name);
+            push_len_prefixed_string(&mut body, &entry_type_name(ty));
+            push_len_prefixed_string(&mut body, metadata);
+
+            encode_control_record(out, timestamp, 0, *target_entry_id, &body);
+        }
+        RecordPayload::Finish { target_entry_id } => {
+            encode_control_record(out, timestamp, 1, *target_entry_id, &[]);
+        }
+        RecordPayload::Value(payload) => encode_record(out, entry_id, timestamp, payload),
+        RecordPayload::Metadata {
+            target_entry_id,
+            metadata,
+        } => {
+            let mut body = vec![];
+            push_len_prefixed_string(&mut body, metadata);
+
+            encode_control_record(out, timestamp, 2, *target_entry_id, &body);
+        }
+    }
+}
+
+/// A builder that emits `Start`/`Value`/`Metadata`/`Finish` control records, tracking the next
+/// free entry id so callers don't have to.
+#[derive(Debug, Default)]
+pub struct DataLogWriter {
+    bytes: Vec<u8>,
+    next_entry_id: u32,
+}
+
+impl DataLogWriter {
+    /// Encodes the header, returning a writer ready to emit entries.
+    pub fn new(metadata: &str) -> Self {
+        let mut bytes = vec![];
+        encode_header(&mut bytes, metadata);
+
+        Self {
+            bytes,
+            // Entry id 0 is reserved for control records.
+            next_entry_id: 1,
+        }
+    }
+
+    /// Emits a `Start` record for a new entry, returning the id to use for subsequent
+    /// `append`/`set_metadata`/`finish` calls.
+    pub fn start(&mut self, timestamp: u64, name: &str, ty: &EntryType, metadata: &str) -> u32 {
+        let entry_id = self.next_entry_id;
+        self.next_entry_id += 1;
+
+        encode_payload(
+            &mut self.bytes,
+            timestamp,
+            0,
+            &RecordPayload::Start {
+                target_entry_id: entry_id,
+                name,
+                ty: ty.clone(),
+                metadata,
+            },
+        );
+
+        entry_id
+    }
+
+    /// Emits a `Value` record for `entry_id`.
+    pub fn append(&mut self, timestamp: u64, entry_id: u32, payload: &[u8]) {
+        encode_record(&mut self.bytes, entry_id, timestamp, payload);
+    }
+
+    /// Emits a `Metadata` record updating `entry_id`'s metadata string.
+    pub fn set_metadata(&mut self, timestamp: u64, entry_id: u32, metadata: &str) {
+        encode_payload(
+            &mut self.bytes,
+            timestamp,
+            0,
+            &RecordPayload::Metadata {
+                target_entry_id: entry_id,
+                metadata,
+            },
+        );
+    }
+
+    /// Emits a `Finish` record for `entry_id`. WPILOG allows the id to be reused afterward.
+    pub fn finish(&mut self, timestamp: u64, entry_id: u32) {
+        encode_payload(
+            &mut self.bytes,
+            timestamp,
+            0,
+            &RecordPayload::Finish {
+                target_entry_id: entry_id,
+            },
+        );
+    }
+
+    /// Consumes the writer, returning the encoded `.wpilog` bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DataLog, RecordPayload};
+
+    #[test]
+    fn test_round_trip() {
+        let mut writer = DataLogWriter::new("test");
+
+        let ts_id = writer.start(1_000_000, "/Timestamp", &EntryType::Int64, "");
+        writer.append(1_000_000, ts_id, &3i64.to_le_bytes());
+        writer.set_metadata(1_000_500, ts_id, "{\"source\":\"NT\"}");
+
+        let name_id = writer.start(1_000_100, "/Robot/Name", &EntryType::String, "{}");
+        writer.append(1_000_200, name_id, "crate".as_bytes());
+
+        let flag_id = writer.start(1_000_300, "/Enabled", &EntryType::Boolean, "");
+        writer.append(1_000_400, flag_id, &[1u8]);
+        writer.finish(1_000_450, flag_id);
+
+        writer.append(2_000_000, ts_id, &7i64.to_le_bytes());
+        writer.finish(2_000_100, ts_id);
+        writer.finish(2_000_200, name_id);
+
+        let bytes = writer.into_bytes();
+        let log = DataLog::parse_from_bytes(&bytes).expect("round-tripped log should parse");
+
+        assert_eq!(log.metadata, "test");
+
+        // Bound outside the `vec!` literal below: a `&3i64.to_le_bytes()` inline inside it
+        // borrows an array temporary whose scope ends at the `let expected = ...;` statement,
+        // which doesn't outlive `expected`'s use in the loop further down.
+        let three_bytes = 3i64.to_le_bytes();
+        let seven_bytes = 7i64.to_le_bytes();
+        let flag_bytes = [1u8];
+
+        let expected = vec![
+            (
+                1_000_000,
+                0,
+                RecordPayload::Start {
+                    target_entry_id: ts_id,
+                    name: "/Timestamp",
+                    ty: EntryType::Int64,
+                    metadata: "",
+                },
+            ),
+            (1_000_000, ts_id, RecordPayload::Value(&three_bytes)),
+            (
+                1_000_500,
+                0,
+                RecordPayload::Metadata {
+                    target_entry_id: ts_id,
+                    metadata: "{\"source\":\"NT\"}",
+                },
+            ),
+            (
+                1_000_100,
+                0,
+                RecordPayload::Start {
+                    target_entry_id: name_id,
+                    name: "/Robot/Name",
+                    ty: EntryType::String,
+                    metadata: "{}",
+                },
+            ),
+            (1_000_200, name_id, RecordPayload::Value("crate".as_bytes())),
+            (
+                1_000_300,
+                0,
+                RecordPayload::Start {
+                    target_entry_id: flag_id,
+                    name: "/Enabled",
+                    ty: EntryType::Boolean,
+                    metadata: "",
+                },
+            ),
+            (1_000_400, flag_id, RecordPayload::Value(&flag_bytes)),
+            (
+                1_000_450,
+                0,
+                RecordPayload::Finish {
+                    target_entry_id: flag_id,
+                },
+            ),
+            (2_000_000, ts_id, RecordPayload::Value(&seven_bytes)),
+            (
+                2_000_100,
+                0,
+                RecordPayload::Finish {
+                    target_entry_id: ts_id,
+                },
+            ),
+            (
+                2_000_200,
+                0,
+                RecordPayload::Finish {
+                    target_entry_id: name_id,
+                },
+            ),
+        ];
+
+        assert_eq!(log.records.len(), expected.len());
+        for (record, (timestamp, entry_id, payload)) in log.records.iter().zip(expected) {
+            assert_eq!(record.timestamp, timestamp);
+            assert_eq!(record.entry_id, entry_id);
+            assert_eq!(record.payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_many_entries_and_value_sizes() {
+        // Exercises every minimal-byte-length boundary the bit-packing nibble has to choose
+        // between: entry ids and payload sizes that need 1 vs 2+ bytes, and a timestamp large
+        // enough to need more than 3.
+        let mut writer = DataLogWriter::new("");
+
+        let mut ids = vec![];
+        for i in 0..300u32 {
+            let ty = if i % 2 == 0 {
+                EntryType::Int64
+            } else {
+                EntryType::Double
+            };
+            ids.push(writer.start(i as u64, &format!("/entry{i}"), &ty, ""));
+        }
+
+        let big_timestamp = 1u64 << 40;
+        let big_payload = vec![0xAB; 300];
+        writer.append(big_timestamp, ids[0], &big_payload);
+
+        let bytes = writer.into_bytes();
+        let log = DataLog::parse_from_bytes(&bytes).expect("round-tripped log should parse");
+
+        assert_eq!(log.records.len(), ids.len() + 1);
+        for (record, (i, &entry_id)) in log.records.iter().zip(ids.iter().enumerate()) {
+            let RecordPayload::Start {
+                target_entry_id,
+                name,
+                ..
+            } = &record.payload
+            else {
+                panic!("expected a Start record");
+            };
+
+            assert_eq!(*target_entry_id, entry_id);
+            assert_eq!(*name, format!("/entry{i}"));
+        }
+
+        let value_record = log.records.last().unwrap();
+        assert_eq!(value_record.entry_id, ids[0]);
+        assert_eq!(value_record.timestamp, big_timestamp);
+        assert_eq!(value_record.payload, RecordPayload::Value(&big_payload));
+    }
+}
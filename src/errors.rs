@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
 use crate::SUPPORTED_VERSION;
 
 #[derive(Debug, Clone, Error)]
@@ -10,4 +13,38 @@ pub enum WPILogParseError {
     TooShort,
     #[error("Unsupported version '{0:?}', expected '{SUPPORTED_VERSION:?}'")]
     UnsupportedVersion([u8; 2]),
+    #[error("Value payload for type \"{ty}\" was {got} bytes, expected {expected}")]
+    InvalidValuePayload {
+        ty: String,
+        expected: usize,
+        got: usize,
+    },
+    #[error("Error while parsing utf8 string: {0}")]
+    Utf8Error(core::str::Utf8Error),
+    /// Only produced by the `std`-only reader-based parse path and by `writer::WPILogWriter`.
+    #[cfg(feature = "std")]
+    #[error("I/O error while reading a .wpilog: {0:?}")]
+    Io(std::io::ErrorKind),
+    #[error("(offset {offset}) Value record referenced unknown entry id {entry_id}")]
+    UnknownEntryId { offset: usize, entry_id: u32 },
+    #[error("Control record payload was malformed")]
+    MalformedControlRecord,
+    #[error("(offset {offset}) Needed {need} bytes for a length-prefixed string but only {have} remained")]
+    TruncatedString {
+        offset: usize,
+        need: usize,
+        have: usize,
+    },
+    #[error("(offset {offset}) Record declared a payload of {payload_size} bytes but only {remaining} remained")]
+    PayloadOverrun {
+        offset: usize,
+        payload_size: usize,
+        remaining: usize,
+    },
+    #[error("(offset {offset}) Needed {need} bytes to read a record's entry id/payload size/timestamp field but only {have} remained")]
+    TruncatedRecordHeader {
+        offset: usize,
+        need: usize,
+        have: usize,
+    },
 }
@@ -1,3 +1,11 @@
+// NOTE: only the in-memory parse path (`read_only`/`parsing`/`errors`/`cooked`) is `alloc`-clean;
+// this crate as a whole still requires `std` (`DataLog`'s nom-based parser, the streaming/async
+// readers, and the encoders all use it unconditionally). Porting those is its own follow-up, so
+// there's no crate-level `#![no_std]` here - `std` off only swaps `read_only`'s entry map for a
+// `BTreeMap` and compiles out the `std::io`-based `OwnedWPILog` path and `WPILogParseError::Io`;
+// it doesn't make the crate buildable without `std`.
+extern crate alloc;
+
 use nom::{
     bytes::complete::{tag, take},
     combinator::{map, map_res},
@@ -7,10 +15,28 @@ use nom::{
     sequence::tuple,
 };
 
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "tokio")]
+pub mod async_stream;
+pub mod cooked;
+pub mod decode;
+pub mod encode;
+pub mod errors;
+pub(crate) mod parsing;
+pub mod read_only;
+pub mod stream;
+pub mod timeline;
+pub mod writer;
+
 /// The supported .wpilog version (major, minor) for this parser.
 pub(crate) const SUPPORTED_VERSION: u16 = 0x0100;
 /// The magic number for the .wpilog file format.
 pub(crate) const WPILOG_MAGIC: &[u8] = b"WPILOG";
+/// The smallest a well-formed .wpilog file can be: magic + version + an empty length-prefixed
+/// extra-header string.
+pub(crate) const MINIMUM_WPILOG_SIZE: usize = WPILOG_MAGIC.len() + 2 + 4;
 
 // Types
 
@@ -31,7 +57,10 @@ pub enum ParsingError<'a> {
     UnsupportedVersion(u16),
     #[error("\"{caller}\" requested {expected} bytes of data but was given only {given} ({:?}) bytes.", given.to_le_bytes())]
     DataTooShortForRequestedLength {
-        caller: &'a str,
+        // Always constructed from a string literal naming the caller, so this doesn't need to
+        // borrow from the data being parsed - see `stream::DataLogReader::next_record`, which
+        // relies on that to decouple a header-parse error from its `&mut self` borrow.
+        caller: &'static str,
         expected: usize,
         given: usize,
     },
@@ -89,9 +118,9 @@ pub struct DataLog<'a> {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Record<'a> {
-    entry_id: u32,
-    timestamp: u64,
-    payload: RecordPayload<'a>,
+    pub(crate) entry_id: u32,
+    pub(crate) timestamp: u64,
+    pub(crate) payload: RecordPayload<'a>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +13,42 @@ macro_rules! extract_from_slice {
     };
 }
 
+/// Runs the given block only when the "tracing" feature is enabled.
+#[macro_export]
+macro_rules! tracing {
+    ($($body:tt)*) => {
+        #[cfg(feature = "tracing")]
+        { $($body)* }
+    };
+}
+
+/// Forwards to `tracing::trace!` when the "tracing" feature is enabled, otherwise a no-op.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)*);
+    };
+}
+
+/// Forwards to `tracing::debug!` when the "tracing" feature is enabled, otherwise a no-op.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+/// Forwards to `tracing::error!` when the "tracing" feature is enabled, otherwise a no-op.
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::error!($($arg)*);
+    };
+}
+
 /// Depending on the "safe" feature, returns an `Err` or `debug_assert`s the condition (using the error message).
 #[macro_export]
 macro_rules! err_if_safe {
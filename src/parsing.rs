@@ -1,37 +1,40 @@
-use std::mem::size_of;
+use core::mem::size_of;
 
-use crate::{error, extract_from_slice, trace};
+use crate::{err_if_safe, errors::WPILogParseError, extract_from_slice, trace};
 
 pub(crate) fn from_utf8_release_unchecked<'a>(data: &'a [u8]) -> &'a str {
     #[cfg(debug_assertions)]
     {
-        std::str::from_utf8(data).expect("Invalid UTF8 String")
+        core::str::from_utf8(data).expect("Invalid UTF8 String")
     }
     #[cfg(not(debug_assertions))]
     unsafe {
-        std::str::from_utf8_unchecked(data)
+        core::str::from_utf8_unchecked(data)
     }
 }
 
 /// Parses a &str from little-endian `u32` length-prefixed utf8 string.
-/// - Bounds checking and UTF8 validation only in debug mode.
+/// - With the `safe` feature, returns a `WPILogParseError::TruncatedString` (carrying the
+///   offset of the failure) instead of ever panicking. Without it, the same conditions only
+///   `debug_assert!`.
 ///
-/// Returns unread bytes and the &str.
+/// Returns the parsed `&str`.
 #[cfg_attr(feature = "tracing", tracing::instrument(skip(data), fields(data_len = data.len())))]
 pub(crate) fn u32_len_prefix_utf8_string_unchecked<'a>(
     data: &'a [u8],
     bytes_read: &mut usize,
-) -> &'a str {
+) -> Result<&'a str, WPILogParseError> {
     const U32_SIZE: usize = size_of::<u32>();
 
-    #[cfg(debug_assertions)]
-    {
-        if data.len() < U32_SIZE {
-            error!("data too short to parse a u32 length-prefixed string");
-            // Already at the end of the data regardless, parsing farther will lead to corrupt results (non-recoverable)
-            panic!("invalid data");
+    err_if_safe!(
+        data.len() < U32_SIZE,
+        WPILogParseError::TruncatedString {
+            offset: *bytes_read,
+            need: U32_SIZE,
+            have: data.len(),
         }
-    }
+    );
+
     let len = u32::from_le_bytes(extract_from_slice!(data, 0, 1, 2, 3)) as usize;
 
     trace!(
@@ -39,20 +42,22 @@ pub(crate) fn u32_len_prefix_utf8_string_unchecked<'a>(
         raw_length_bytes = ?extract_from_slice!(data, 0, 1, 2, 3)
     );
 
-    #[cfg(debug_assertions)]
-    {
-        if data.len() < U32_SIZE + len {
-            error!("data too short to parse the length of the string");
-            panic!("invalid data");
+    err_if_safe!(
+        data.len() < U32_SIZE + len,
+        WPILogParseError::TruncatedString {
+            offset: *bytes_read + U32_SIZE,
+            need: len,
+            have: data.len().saturating_sub(U32_SIZE),
         }
-    }
+    );
+
     let str = from_utf8_release_unchecked(&data[U32_SIZE..U32_SIZE + len]);
 
     trace!(str);
 
     *bytes_read += U32_SIZE + len;
 
-    str
+    Ok(str)
 }
 
 /// Parses a little-endian u32.
@@ -106,3 +111,44 @@ pub(crate) fn variable_length_u64<'a>(data: &'a [u8], len: usize) -> u64 {
         data[7] & ((7 >= len) as u8).wrapping_sub(1),
     ])
 }
+
+// `err_if_safe!` only ever returns `Err` (rather than `debug_assert!`ing) under the "safe"
+// feature, so these offset assertions only hold with it enabled.
+#[cfg(feature = "safe")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncated_string_length_prefix_reports_offset() {
+        let mut bytes_read = 10;
+        let err = u32_len_prefix_utf8_string_unchecked(&[0u8; 2], &mut bytes_read).unwrap_err();
+
+        assert!(matches!(
+            err,
+            WPILogParseError::TruncatedString {
+                offset: 10,
+                need: 4,
+                have: 2,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_truncated_string_body_reports_offset() {
+        let mut bytes_read = 10;
+        let mut data = 5u32.to_le_bytes().to_vec();
+        data.extend_from_slice(b"ab"); // claims 5 bytes, only 2 follow
+
+        let err = u32_len_prefix_utf8_string_unchecked(&data, &mut bytes_read).unwrap_err();
+
+        assert!(matches!(
+            err,
+            WPILogParseError::TruncatedString {
+                offset: 14, // bytes_read (10) + the 4-byte length prefix
+                need: 5,
+                have: 2,
+            }
+        ));
+    }
+}
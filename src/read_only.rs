@@ -1,4 +1,12 @@
+#[cfg(feature = "std")]
 use std::collections::{hash_map::Keys, HashMap};
+#[cfg(feature = "std")]
+use std::io::{ErrorKind, Read};
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{btree_map::Keys, BTreeMap as HashMap};
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
 
 use crate::{
     debug,
@@ -6,7 +14,7 @@ use crate::{
     parsing::{
         u32, u32_len_prefix_utf8_string_unchecked, variable_length_u32, variable_length_u64,
     },
-    trace, tracing, MINIMUM_WPILOG_SIZE, SUPPORTED_VERSION, WPILOG_MAGIC,
+    err_if_safe, trace, tracing, MINIMUM_WPILOG_SIZE, SUPPORTED_VERSION, WPILOG_MAGIC,
 };
 
 // TYPES
@@ -15,12 +23,15 @@ pub type Timestamp = u64;
 
 // WPILOG
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct WPILog<'a> {
     /// File header metadata
     pub(crate) metadata: &'a str,
-    /// Name correlated entries
-    pub(crate) entries: HashMap<&'a str, Entry<'a>>,
+    /// Entries keyed by their wire entry id, so that two ids concurrently bound to the same
+    /// name (one not yet `Finish`ed when the other `Start`s) don't clobber each other's values.
+    pub(crate) entries: HashMap<u32, Entry<'a>>,
+    /// The id most recently `Start`ed under each name, for name-based lookup.
+    name_to_id: HashMap<&'a str, u32>,
     /// Backing data
     data: &'a [u8],
 }
@@ -44,14 +55,14 @@ impl<'a> WPILog<'a> {
             }
             *bytes_read += 6;
 
-            let version = [data[7], data[6]]; // LE swap
+            let version = u16::from_le_bytes([data[6], data[7]]);
             if version != SUPPORTED_VERSION {
-                return Err(WPILogParseError::UnsupportedVersion(version));
+                return Err(WPILogParseError::UnsupportedVersion([data[7], data[6]]));
             }
             *bytes_read += 2;
         }
 
-        let metadata = u32_len_prefix_utf8_string_unchecked(&data[*bytes_read..], bytes_read);
+        let metadata = u32_len_prefix_utf8_string_unchecked(&data[*bytes_read..], bytes_read)?;
 
         Ok(metadata)
     }
@@ -65,8 +76,8 @@ impl<'a> WPILog<'a> {
 
         debug!("Verified header: {metadata:?}");
 
-        let mut id_name: HashMap<u32, &str> = HashMap::new();
-        let mut entries: HashMap<&str, Entry> = HashMap::new();
+        let mut entries: HashMap<u32, Entry> = HashMap::new();
+        let mut name_to_id: HashMap<&str, u32> = HashMap::new();
 
         tracing! {
             let mut first = std::time::Instant::now();
@@ -92,12 +103,49 @@ impl<'a> WPILog<'a> {
 
             trace!(entry_id_length, payload_size_length, timestamp_length);
 
+            // `variable_length_u32`/`variable_length_u64` always index a fixed 4/8-byte window
+            // of the slice they're given (zeroing out whichever of those bytes the nibble-coded
+            // length says aren't meaningful), so - regardless of how short `entry_id_length` and
+            // `payload_size_length` claim to be - the underlying slice must actually hold that
+            // many bytes, or indexing into it panics.
+            err_if_safe!(
+                data.len().saturating_sub(bytes_read) < 4,
+                WPILogParseError::TruncatedRecordHeader {
+                    offset: bytes_read,
+                    need: 4,
+                    have: data.len().saturating_sub(bytes_read),
+                }
+            );
             let entry_id = variable_length_u32(&data[bytes_read..], entry_id_length);
             bytes_read += entry_id_length;
+
+            err_if_safe!(
+                data.len().saturating_sub(bytes_read) < 4,
+                WPILogParseError::TruncatedRecordHeader {
+                    offset: bytes_read,
+                    need: 4,
+                    have: data.len().saturating_sub(bytes_read),
+                }
+            );
             let payload_size =
                 variable_length_u32(&data[bytes_read..], payload_size_length) as usize;
             bytes_read += payload_size_length;
-            let timestamp = variable_length_u64(&data[bytes_read..], timestamp_length);
+
+            // A short trailing record can leave fewer than 8 bytes after the timestamp's start,
+            // so only `timestamp_length` bytes are required; they're read through a zero-padded
+            // copy instead of indexing `data` directly.
+            err_if_safe!(
+                data.len().saturating_sub(bytes_read) < timestamp_length,
+                WPILogParseError::TruncatedRecordHeader {
+                    offset: bytes_read,
+                    need: timestamp_length,
+                    have: data.len().saturating_sub(bytes_read),
+                }
+            );
+            let mut timestamp_window = [0u8; 8];
+            let available = data.len().saturating_sub(bytes_read).min(8);
+            timestamp_window[..available].copy_from_slice(&data[bytes_read..bytes_read + available]);
+            let timestamp = variable_length_u64(&timestamp_window, timestamp_length);
             bytes_read += timestamp_length;
 
             trace!(entry_id, payload_size, timestamp);
@@ -106,10 +154,22 @@ impl<'a> WPILog<'a> {
                 record_count += 1;
             }
 
+            err_if_safe!(
+                bytes_read + payload_size > len,
+                WPILogParseError::PayloadOverrun {
+                    offset: bytes_read,
+                    payload_size,
+                    remaining: len - bytes_read,
+                }
+            );
+
             if entry_id != 0 {
                 entries
-                    .get_mut(&id_name[&entry_id])
-                    .unwrap()
+                    .get_mut(&entry_id)
+                    .ok_or(WPILogParseError::UnknownEntryId {
+                        offset: bytes_read,
+                        entry_id,
+                    })?
                     .add_value(timestamp, &data[bytes_read..bytes_read + payload_size]);
                 bytes_read += payload_size;
 
@@ -120,26 +180,52 @@ impl<'a> WPILog<'a> {
                 entry_count += 1;
             }
 
+            // Where this record's payload began, so we can always land exactly at its end
+            // regardless of which (or how much) of the control-record branches below run.
+            let payload_start = bytes_read;
+
             let control_record_type = data[bytes_read];
             bytes_read += 1;
 
             let target_entry_id = u32(&data[bytes_read..bytes_read + 4]);
             bytes_read += 4;
 
-            if control_record_type == 0 {
-                if id_name.contains_key(&target_entry_id) {
-                    unimplemented!("This parser does not support entry_id rebindings.");
+            match control_record_type {
+                0 => {
+                    // Start. Re-pointing an already-used entry id to a new entry is allowed, and
+                    // so is a second id Starting under a name an unfinished id already holds -
+                    // entries are keyed by id, so neither clobbers the other's values; name-based
+                    // lookup simply follows whichever id most recently claimed the name.
+                    let name =
+                        u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read)?;
+                    let ty =
+                        u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read)?;
+                    let metadata =
+                        u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read)?;
+
+                    name_to_id.insert(name, target_entry_id);
+                    entries.insert(target_entry_id, Entry::new(name, ty, metadata));
                 }
-
-                let name =
-                    u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read);
-                let ty = u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read);
-                let metadata =
-                    u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read);
-
-                id_name.insert(target_entry_id, name);
-                entries.insert(name, Entry::new(name, ty, metadata));
+                1 => {
+                    // Finish.
+                    if let Some(entry) = entries.get_mut(&target_entry_id) {
+                        entry.mark_finished(timestamp);
+                    }
+                }
+                2 => {
+                    // SetMetadata.
+                    let metadata =
+                        u32_len_prefix_utf8_string_unchecked(&data[bytes_read..], &mut bytes_read)?;
+
+                    if let Some(entry) = entries.get_mut(&target_entry_id) {
+                        entry.set_metadata(metadata);
+                    }
+                }
+                _ => {}
             }
+
+            // Unknown/future control types (or any drift above) shouldn't desync the stream.
+            bytes_read = payload_start + payload_size;
         }
 
         tracing! {
@@ -164,6 +250,7 @@ impl<'a> WPILog<'a> {
         let log = Self {
             metadata,
             entries,
+            name_to_id,
             data,
         };
 
@@ -174,12 +261,12 @@ impl<'a> WPILog<'a> {
 
     // Getters
 
-    pub fn get_entry_names(&self) -> Keys<'_, &str, Entry> {
-        self.entries.keys()
+    pub fn get_entry_names(&self) -> Keys<'_, &str, u32> {
+        self.name_to_id.keys()
     }
 
     pub fn get(&self, index: &str) -> Option<&Entry<'a>> {
-        self.entries.get(index)
+        self.entries.get(self.name_to_id.get(index)?)
     }
 }
 
@@ -191,6 +278,7 @@ pub struct Entry<'a> {
     ty: &'a str,
     metadata: &'a str,
     values: Vec<(Timestamp, &'a [u8])>,
+    finished_at: Option<Timestamp>,
 }
 
 impl<'a> Entry<'a> {
@@ -200,6 +288,7 @@ impl<'a> Entry<'a> {
             ty,
             metadata,
             values: vec![],
+            finished_at: None,
         }
     }
 
@@ -211,7 +300,594 @@ impl<'a> Entry<'a> {
         self.values.sort_by_key(|(t, _)| *t);
     }
 
-    pub fn get_value(&self, idx: usize) -> Option<&(Timestamp, &[u8])> {
+    fn mark_finished(&mut self, timestamp: Timestamp) {
+        self.finished_at = Some(timestamp);
+    }
+
+    fn set_metadata(&mut self, metadata: &'a str) {
+        self.metadata = metadata;
+    }
+
+    pub fn get_value(&self, idx: usize) -> Option<&(Timestamp, &'a [u8])> {
+        self.values.get(idx)
+    }
+
+    /// The most recent value at or before `ts` ("sample-and-hold" semantics), or `None` if
+    /// `ts` precedes every recorded value.
+    ///
+    /// `O(log n)` via binary search over `values`, which `parse` keeps sorted by timestamp.
+    pub fn value_at(&self, ts: Timestamp) -> Option<&(Timestamp, &'a [u8])> {
+        // Partition point rather than `binary_search_by_key`, so that among several values
+        // sharing a timestamp the last-logged one is returned.
+        let idx = self.values.partition_point(|(t, _)| *t <= ts).checked_sub(1)?;
+
+        self.values.get(idx)
+    }
+
+    /// The contiguous slice of values in the half-open interval `[start, end)`.
+    ///
+    /// `O(log n)` via partition point over `values`, which `parse` keeps sorted by timestamp.
+    /// `binary_search_by_key` would land on *any* matching element when timestamps repeat
+    /// (routine in WPILOG, where multiple entries are logged in the same tick), silently
+    /// dropping in-range samples either side of whichever one it happened to find.
+    pub fn range(&self, start: Timestamp, end: Timestamp) -> &[(Timestamp, &'a [u8])] {
+        let lo = self.values.partition_point(|(t, _)| *t < start);
+        let hi = self.values.partition_point(|(t, _)| *t < end);
+
+        &self.values[lo..hi]
+    }
+
+    /// The earliest recorded value, if any.
+    pub fn first(&self) -> Option<&(Timestamp, &'a [u8])> {
+        self.values.first()
+    }
+
+    /// The most recent recorded value, if any.
+    pub fn last(&self) -> Option<&(Timestamp, &'a [u8])> {
+        self.values.last()
+    }
+
+    /// The WPILOG type string this entry was declared with (e.g. `"int64"`).
+    pub fn ty(&self) -> &'a str {
+        self.ty
+    }
+
+    /// The timestamp of this entry's `Finish` control record, if one has been seen.
+    pub fn finished_at(&self) -> Option<Timestamp> {
+        self.finished_at
+    }
+}
+
+// STREAMING (owned) PARSE
+//
+// This section is `std`-only: it builds on `std::io::Read`, which has no `core`/`alloc`
+// equivalent.
+
+/// An owned, allocation-backed counterpart to [`WPILog`] produced by [`OwnedWPILog::parse_reader`].
+///
+/// Unlike `WPILog<'a>`, this doesn't borrow from a backing slice, which is what lets it be
+/// built incrementally from a [`Read`] that may not hold the whole file in memory.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct OwnedWPILog {
+    pub metadata: String,
+    /// Entries keyed by their wire entry id, for the same reason as `WPILog::entries` above.
+    entries: HashMap<u32, OwnedEntry>,
+    /// The id most recently `Start`ed under each name, for name-based lookup.
+    name_to_id: HashMap<String, u32>,
+}
+
+#[cfg(feature = "std")]
+impl OwnedWPILog {
+    fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool, WPILogParseError> {
+        match reader.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(WPILogParseError::Io(e.kind())),
+        }
+    }
+
+    fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), WPILogParseError> {
+        reader
+            .read_exact(buf)
+            .map_err(|e| WPILogParseError::Io(e.kind()))
+    }
+
+    /// Reads a variable-length (1-4 byte), little-endian `u32` off of `reader`.
+    fn read_variable_u32<R: Read>(reader: &mut R, len: usize) -> Result<u32, WPILogParseError> {
+        let mut buf = [0u8; 4];
+        Self::read_exact(reader, &mut buf[..len])?;
+        Ok(variable_length_u32(&buf, len))
+    }
+
+    /// Reads a variable-length (1-8 byte), little-endian `u64` off of `reader`.
+    fn read_variable_u64<R: Read>(reader: &mut R, len: usize) -> Result<u64, WPILogParseError> {
+        let mut buf = [0u8; 8];
+        Self::read_exact(reader, &mut buf[..len])?;
+        Ok(variable_length_u64(&buf, len))
+    }
+
+    fn read_len_prefixed_string(
+        payload: &[u8],
+        offset: &mut usize,
+    ) -> Result<String, WPILogParseError> {
+        let remaining = payload
+            .get(*offset..)
+            .ok_or(WPILogParseError::MalformedControlRecord)?;
+
+        let mut consumed = 0;
+        let s = u32_len_prefix_utf8_string_unchecked(remaining, &mut consumed)?;
+        *offset += consumed;
+
+        Ok(s.to_string())
+    }
+
+    /// Reads the magic, version, and extra-header string off of `reader`.
+    fn read_header<R: Read>(reader: &mut R) -> Result<String, WPILogParseError> {
+        let mut magic = [0u8; 6];
+        Self::read_exact(reader, &mut magic)?;
+        if &magic[..] != WPILOG_MAGIC {
+            return Err(WPILogParseError::InvalidMagic);
+        }
+
+        let mut version = [0u8; 2];
+        Self::read_exact(reader, &mut version)?;
+        if u16::from_le_bytes(version) != SUPPORTED_VERSION {
+            return Err(WPILogParseError::UnsupportedVersion([version[1], version[0]]));
+        }
+
+        let mut len_buf = [0u8; 4];
+        Self::read_exact(reader, &mut len_buf)?;
+        let len = u32::from_le_bytes(len_buf) as usize;
+
+        let mut metadata = vec![0u8; len];
+        Self::read_exact(reader, &mut metadata)?;
+
+        String::from_utf8(metadata).map_err(|e| WPILogParseError::Utf8Error(e.utf8_error()))
+    }
+
+    /// Incrementally parses a `.wpilog` off of any [`Read`], without requiring the whole file
+    /// to be resident in memory.
+    ///
+    /// Returns an error the moment `reader` ends in the middle of a record; reaching end of
+    /// file exactly on a record boundary is the normal, successful end of the log.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn parse_reader<R: Read>(mut reader: R) -> Result<Self, WPILogParseError> {
+        let metadata = Self::read_header(&mut reader)?;
+
+        let mut entries: HashMap<u32, OwnedEntry> = HashMap::new();
+        let mut name_to_id: HashMap<String, u32> = HashMap::new();
+        let mut bytes_read = MINIMUM_WPILOG_SIZE;
+
+        loop {
+            let record_start = bytes_read;
+
+            let mut lengths = [0u8; 1];
+            if !Self::read_exact_or_eof(&mut reader, &mut lengths)? {
+                break;
+            }
+            bytes_read += 1;
+
+            let entry_id_length = (lengths[0] & 0b11) as usize + 1;
+            let payload_size_length = ((lengths[0] & 0b11 << 2) >> 2) as usize + 1;
+            let timestamp_length = ((lengths[0] & 0b111 << 4) >> 4) as usize + 1;
+
+            let entry_id = Self::read_variable_u32(&mut reader, entry_id_length)?;
+            bytes_read += entry_id_length;
+            let payload_size = Self::read_variable_u32(&mut reader, payload_size_length)? as usize;
+            bytes_read += payload_size_length;
+            let timestamp = Self::read_variable_u64(&mut reader, timestamp_length)?;
+            bytes_read += timestamp_length;
+
+            let mut payload = vec![0u8; payload_size];
+            Self::read_exact(&mut reader, &mut payload)?;
+            bytes_read += payload_size;
+
+            if entry_id != 0 {
+                entries
+                    .get_mut(&entry_id)
+                    .ok_or(WPILogParseError::UnknownEntryId {
+                        offset: record_start,
+                        entry_id,
+                    })?
+                    .add_value(timestamp, payload);
+                continue;
+            }
+
+            if payload.len() < 5 {
+                return Err(WPILogParseError::MalformedControlRecord);
+            }
+
+            let control_record_type = payload[0];
+            let target_entry_id = u32::from_le_bytes([payload[1], payload[2], payload[3], payload[4]]);
+
+            match control_record_type {
+                0 => {
+                    // Start. Re-pointing an already-used entry id to a new entry is allowed, and
+                    // so is a second id Starting under a name an unfinished id already holds -
+                    // entries are keyed by id, so neither clobbers the other's values; name-based
+                    // lookup simply follows whichever id most recently claimed the name.
+                    let mut offset = 5;
+                    let name = Self::read_len_prefixed_string(&payload, &mut offset)?;
+                    let ty = Self::read_len_prefixed_string(&payload, &mut offset)?;
+                    let record_metadata = Self::read_len_prefixed_string(&payload, &mut offset)?;
+
+                    name_to_id.insert(name.clone(), target_entry_id);
+                    entries.insert(target_entry_id, OwnedEntry::new(name, ty, record_metadata));
+                }
+                1 => {
+                    // Finish.
+                    if let Some(entry) = entries.get_mut(&target_entry_id) {
+                        entry.mark_finished(timestamp);
+                    }
+                }
+                2 => {
+                    // SetMetadata.
+                    let mut offset = 5;
+                    let record_metadata = Self::read_len_prefixed_string(&payload, &mut offset)?;
+
+                    if let Some(entry) = entries.get_mut(&target_entry_id) {
+                        entry.set_metadata(record_metadata);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        entries.values_mut().for_each(|e| e.sort_by_timestamp());
+
+        Ok(Self {
+            metadata,
+            entries,
+            name_to_id,
+        })
+    }
+
+    pub fn get_entry_names(&self) -> Keys<'_, String, u32> {
+        self.name_to_id.keys()
+    }
+
+    pub fn get(&self, index: &str) -> Option<&OwnedEntry> {
+        self.entries.get(self.name_to_id.get(index)?)
+    }
+}
+
+// OWNED ENTRY
+
+/// The owned counterpart to [`Entry`], holding its own copy of the entry's payloads.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct OwnedEntry {
+    name: String,
+    ty: String,
+    metadata: String,
+    values: Vec<(Timestamp, Vec<u8>)>,
+    finished_at: Option<Timestamp>,
+}
+
+#[cfg(feature = "std")]
+impl OwnedEntry {
+    fn new(name: String, ty: String, metadata: String) -> Self {
+        Self {
+            name,
+            ty,
+            metadata,
+            values: vec![],
+            finished_at: None,
+        }
+    }
+
+    fn add_value(&mut self, timestamp: Timestamp, value: Vec<u8>) {
+        self.values.push((timestamp, value));
+    }
+
+    fn sort_by_timestamp(&mut self) {
+        self.values.sort_by_key(|(t, _)| *t);
+    }
+
+    fn mark_finished(&mut self, timestamp: Timestamp) {
+        self.finished_at = Some(timestamp);
+    }
+
+    fn set_metadata(&mut self, metadata: String) {
+        self.metadata = metadata;
+    }
+
+    pub fn get_value(&self, idx: usize) -> Option<&(Timestamp, Vec<u8>)> {
         self.values.get(idx)
     }
+
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// The timestamp of this entry's `Finish` control record, if one has been seen.
+    pub fn finished_at(&self) -> Option<Timestamp> {
+        self.finished_at
+    }
+
+    pub fn metadata(&self) -> &str {
+        &self.metadata
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod owned_tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn wpilog_bytes() -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(WPILOG_MAGIC);
+        bytes.extend_from_slice(&SUPPORTED_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // empty extra-header string
+
+        // Start entry id 1, "/Counter", "int64", metadata "".
+        let mut start_payload = vec![0u8, 1, 0, 0, 0]; // control type 0, target id 1
+        start_payload.extend_from_slice(&8u32.to_le_bytes());
+        start_payload.extend_from_slice(b"/Counter");
+        start_payload.extend_from_slice(&5u32.to_le_bytes());
+        start_payload.extend_from_slice(b"int64");
+        start_payload.extend_from_slice(&0u32.to_le_bytes());
+        bytes.push(0b0010_0000); // entry id len 1, size len 1, timestamp len 3
+        bytes.push(0); // record's own entry id = 0 (control record)
+        bytes.push(start_payload.len() as u8);
+        bytes.extend_from_slice(&1_000_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&start_payload);
+
+        // Value for entry id 1.
+        bytes.push(0b0010_0000);
+        bytes.push(1);
+        bytes.push(8);
+        bytes.extend_from_slice(&1_000_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&3i64.to_le_bytes());
+
+        // SetMetadata for entry id 1.
+        let mut metadata_payload = vec![2u8, 1, 0, 0, 0]; // control type 2, target id 1
+        metadata_payload.extend_from_slice(&4u32.to_le_bytes());
+        metadata_payload.extend_from_slice(b"json");
+        bytes.push(0b0010_0000);
+        bytes.push(0);
+        bytes.push(metadata_payload.len() as u8);
+        bytes.extend_from_slice(&1_500_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&metadata_payload);
+
+        // Finish entry id 1.
+        let finish_payload = vec![1u8, 1, 0, 0, 0]; // control type 1, target id 1
+        bytes.push(0b0010_0000);
+        bytes.push(0);
+        bytes.push(finish_payload.len() as u8);
+        bytes.extend_from_slice(&2_000_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&finish_payload);
+
+        bytes
+    }
+
+    #[test]
+    fn test_parse_reader_handles_finish_and_set_metadata() {
+        let log = OwnedWPILog::parse_reader(Cursor::new(wpilog_bytes())).expect("should parse");
+
+        let entry = log.get("/Counter").expect("entry should exist");
+        assert_eq!(entry.get_value(0), Some(&(1_000_000, vec![3, 0, 0, 0, 0, 0, 0, 0])));
+        assert_eq!(entry.metadata(), "json");
+        assert_eq!(entry.finished_at(), Some(2_000_000));
+    }
+
+    #[test]
+    fn test_parse_reader_basic_start_and_value() {
+        // Just a header, a Start, and a Value - no Finish/SetMetadata involved.
+        let mut bytes = vec![];
+        bytes.extend_from_slice(WPILOG_MAGIC);
+        bytes.extend_from_slice(&SUPPORTED_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut start_payload = vec![0u8, 1, 0, 0, 0];
+        start_payload.extend_from_slice(&8u32.to_le_bytes());
+        start_payload.extend_from_slice(b"/Counter");
+        start_payload.extend_from_slice(&5u32.to_le_bytes());
+        start_payload.extend_from_slice(b"int64");
+        start_payload.extend_from_slice(&0u32.to_le_bytes());
+        bytes.push(0b0010_0000);
+        bytes.push(0);
+        bytes.push(start_payload.len() as u8);
+        bytes.extend_from_slice(&1_000_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&start_payload);
+
+        bytes.push(0b0010_0000);
+        bytes.push(1);
+        bytes.push(8);
+        bytes.extend_from_slice(&1_000_000u32.to_le_bytes()[..3]);
+        bytes.extend_from_slice(&3i64.to_le_bytes());
+
+        let log = OwnedWPILog::parse_reader(Cursor::new(bytes)).expect("should parse");
+
+        assert_eq!(
+            log.get_entry_names().map(String::as_str).collect::<Vec<_>>(),
+            vec!["/Counter"]
+        );
+
+        let entry = log.get("/Counter").expect("entry should exist");
+        assert_eq!(entry.ty(), "int64");
+        assert_eq!(
+            entry.get_value(0),
+            Some(&(1_000_000, vec![3, 0, 0, 0, 0, 0, 0, 0]))
+        );
+        assert_eq!(entry.finished_at(), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_with(values: &[(Timestamp, &'static [u8])]) -> Entry<'static> {
+        let mut entry = Entry::new("name", "int64", "");
+        for &(t, v) in values {
+            entry.add_value(t, v);
+        }
+        entry.sort_by_timestamp();
+        entry
+    }
+
+    #[test]
+    fn test_range_with_duplicate_timestamps() {
+        // Several entries logged at the same timestamp is routine in WPILOG.
+        let entry = entry_with(&[
+            (1, &[b'a']),
+            (5, &[b'b']),
+            (5, &[b'c']),
+            (5, &[b'd']),
+            (9, &[b'e']),
+        ]);
+
+        let values: Vec<u8> = entry
+            .range(5, 9)
+            .iter()
+            .map(|(_, v)| v[0])
+            .collect();
+
+        assert_eq!(values, vec![b'b', b'c', b'd']);
+    }
+
+    #[test]
+    fn test_value_at() {
+        let entry = entry_with(&[(1, &[1]), (5, &[5]), (9, &[9])]);
+
+        assert_eq!(entry.value_at(0), None);
+        assert_eq!(entry.value_at(1), Some(&(1, &[1][..])));
+        assert_eq!(entry.value_at(4), Some(&(1, &[1][..])));
+        assert_eq!(entry.value_at(5), Some(&(5, &[5][..])));
+        assert_eq!(entry.value_at(100), Some(&(9, &[9][..])));
+    }
+
+    fn header() -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(WPILOG_MAGIC);
+        bytes.extend_from_slice(&SUPPORTED_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes
+    }
+
+    // `err_if_safe!` only ever returns `Err` (rather than `debug_assert!`ing) under the "safe"
+    // feature, so these offset assertions only hold with it enabled.
+    #[cfg(feature = "safe")]
+    #[test]
+    fn test_unknown_entry_id_reports_offset() {
+        let mut bytes = header();
+        bytes.push(0b0010_0000); // entry id len 1, size len 1, timestamp len 3
+        bytes.push(5); // entry id 5, never Started
+        bytes.push(1); // payload size 1
+        bytes.extend_from_slice(&1_000u32.to_le_bytes()[..3]);
+        bytes.push(0xFF); // payload
+
+        let err = WPILog::parse(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            WPILogParseError::UnknownEntryId {
+                offset: 18,
+                entry_id: 5,
+            }
+        ));
+    }
+
+    #[cfg(feature = "safe")]
+    #[test]
+    fn test_payload_overrun_reports_offset() {
+        let mut bytes = header();
+        bytes.push(0b0010_0000);
+        bytes.push(5);
+        bytes.push(250); // declares a 250-byte payload
+        bytes.extend_from_slice(&1_000u32.to_le_bytes()[..3]);
+        bytes.push(0xFF); // only 1 byte actually follows
+
+        let err = WPILog::parse(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            WPILogParseError::PayloadOverrun {
+                offset: 18,
+                payload_size: 250,
+                remaining: 1,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_multiple_entries() {
+        // Exercises the `HashMap`/`BTreeMap`-backed entry map across the std/alloc split from
+        // a plain multi-entry parse, independent of any single entry's values.
+        let mut bytes = header();
+
+        for (id, name) in [(1u32, "/A"), (2u32, "/B")] {
+            let mut start_payload = vec![0u8];
+            start_payload.extend_from_slice(&id.to_le_bytes());
+            start_payload.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            start_payload.extend_from_slice(name.as_bytes());
+            start_payload.extend_from_slice(&5u32.to_le_bytes());
+            start_payload.extend_from_slice(b"int64");
+            start_payload.extend_from_slice(&0u32.to_le_bytes());
+
+            bytes.push(0b0010_0000);
+            bytes.push(0);
+            bytes.push(start_payload.len() as u8);
+            bytes.extend_from_slice(&1_000u32.to_le_bytes()[..3]);
+            bytes.extend_from_slice(&start_payload);
+        }
+
+        let log = WPILog::parse(&bytes).expect("should parse");
+
+        let mut names: Vec<&str> = log.get_entry_names().copied().collect();
+        names.sort();
+        assert_eq!(names, vec!["/A", "/B"]);
+    }
+
+    #[test]
+    fn test_two_ids_sharing_a_name_dont_clobber_each_others_values() {
+        // Id 1 Starts "/X" and gets a Value, then - while still unfinished - id 2 also Starts
+        // "/X". Entries are keyed by id internally, so id 1's prior and subsequent Values must
+        // stay attributed to id 1, not vanish or get misattributed to id 2.
+        fn start(bytes: &mut Vec<u8>, id: u32, name: &str, ts: u32) {
+            let mut payload = vec![0u8];
+            payload.extend_from_slice(&id.to_le_bytes());
+            payload.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            payload.extend_from_slice(name.as_bytes());
+            payload.extend_from_slice(&5u32.to_le_bytes());
+            payload.extend_from_slice(b"int64");
+            payload.extend_from_slice(&0u32.to_le_bytes());
+
+            bytes.push(0b0010_0000);
+            bytes.push(0);
+            bytes.push(payload.len() as u8);
+            bytes.extend_from_slice(&ts.to_le_bytes()[..3]);
+            bytes.extend_from_slice(&payload);
+        }
+
+        fn value(bytes: &mut Vec<u8>, id: u32, v: i64, ts: u32) {
+            bytes.push(0b0010_0000);
+            bytes.push(id as u8);
+            bytes.push(8);
+            bytes.extend_from_slice(&ts.to_le_bytes()[..3]);
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let mut bytes = header();
+        start(&mut bytes, 1, "/X", 1_000);
+        value(&mut bytes, 1, 3, 1_100);
+        start(&mut bytes, 2, "/X", 1_200);
+        value(&mut bytes, 1, 9, 1_300);
+        value(&mut bytes, 2, 5, 1_400);
+
+        let log = WPILog::parse(&bytes).expect("should parse");
+
+        let id1 = log.entries.get(&1).expect("id 1's entry should still exist");
+        assert_eq!(id1.get_value(0), Some(&(1_100, &3i64.to_le_bytes()[..])));
+        assert_eq!(id1.get_value(1), Some(&(1_300, &9i64.to_le_bytes()[..])));
+
+        let id2 = log.entries.get(&2).expect("id 2's entry should exist");
+        assert_eq!(id2.get_value(0), Some(&(1_400, &5i64.to_le_bytes()[..])));
+
+        // Name-based lookup follows whichever id most recently claimed the name.
+        assert_eq!(log.get("/X").unwrap().get_value(0), Some(&(1_400, &5i64.to_le_bytes()[..])));
+    }
 }
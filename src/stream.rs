@@ -0,0 +1,217 @@
+//! An incremental parser for logs that aren't fully available up front — e.g. one being
+//! tailed off a socket or a file a robot is still writing.
+//!
+//! [`DataLogReader`] is fed bytes via [`push`](DataLogReader::push) and records are pulled off
+//! with [`next_record`](DataLogReader::next_record), which distinguishes "not enough data
+//! buffered yet" (`Ok(None)`) from "malformed" (`Err`). The unconsumed tail of the buffer is
+//! retained across calls, so a record split across two chunks parses whole once the rest
+//! arrives rather than being dropped or reparsed.
+
+use crate::{parse_header, parse_record, ParsingError, Record, WPILOG_MAGIC};
+
+/// Rebuilds a header-parse error under a fresh lifetime, independent of the buffer borrow it
+/// came from.
+///
+/// `parse_header` can only ever fail with one of the variants reconstructed below (none of
+/// which actually borrow from the input being parsed), so this never hits the `unreachable!`.
+/// It exists purely to break the borrow-checker conflict between `e` (tied to `&self.buffer`
+/// by `next_record`'s elided return lifetime) and the `self.buffer.drain` that follows it.
+fn rehome_header_error<'s>(e: ParsingError<'_>) -> ParsingError<'s> {
+    match e {
+        ParsingError::InvalidMagic => ParsingError::InvalidMagic,
+        ParsingError::UnsupportedVersion(v) => ParsingError::UnsupportedVersion(v),
+        ParsingError::DataTooShortForRequestedLength {
+            caller,
+            expected,
+            given,
+        } => ParsingError::DataTooShortForRequestedLength {
+            caller,
+            expected,
+            given,
+        },
+        ParsingError::Utf8Error(e) => ParsingError::Utf8Error(e),
+        ParsingError::Nom(kind) => ParsingError::Nom(kind),
+        _ => unreachable!("parse_header never produces this ParsingError variant"),
+    }
+}
+
+/// The length of a complete header once enough of it is buffered: magic + version + the
+/// length-prefixed metadata string.
+fn header_len(buffer: &[u8]) -> Option<usize> {
+    const PREFIX: usize = WPILOG_MAGIC.len() + 2 + 4;
+
+    if buffer.len() < PREFIX {
+        return None;
+    }
+
+    let metadata_len =
+        u32::from_le_bytes(buffer[PREFIX - 4..PREFIX].try_into().unwrap()) as usize;
+    let total = PREFIX + metadata_len;
+
+    (buffer.len() >= total).then_some(total)
+}
+
+/// The length of a complete record once enough of it is buffered: the leading bitfield, the
+/// three variable-length fields, and the payload.
+fn record_len(buffer: &[u8]) -> Option<usize> {
+    let lengths = *buffer.first()?;
+
+    let id_len = (lengths & 0b11) as usize + 1;
+    let size_len = ((lengths & 0b11 << 2) >> 2) as usize + 1;
+    let ts_len = ((lengths & 0b111 << 4) >> 4) as usize + 1;
+    let header_len = 1 + id_len + size_len + ts_len;
+
+    if buffer.len() < header_len {
+        return None;
+    }
+
+    let mut size_bytes = [0u8; 4];
+    size_bytes[..size_len].copy_from_slice(&buffer[1 + id_len..1 + id_len + size_len]);
+    let payload_size = u32::from_le_bytes(size_bytes) as usize;
+
+    let total = header_len + payload_size;
+
+    (buffer.len() >= total).then_some(total)
+}
+
+/// An incremental `.wpilog` reader that can be fed chunks as they arrive.
+#[derive(Debug, Default)]
+pub struct DataLogReader {
+    buffer: Vec<u8>,
+    /// Bytes at the front of `buffer` that were consumed by the last successful parse, and
+    /// can be dropped the next time we're not holding a borrow into `buffer`.
+    consumed: usize,
+    metadata: Option<String>,
+}
+
+impl DataLogReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffers more bytes read off of the underlying source.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// The log's extra-header string, once the header has been parsed.
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
+    /// Parses the next complete record out of the buffered data.
+    ///
+    /// Returns `Ok(None)` when the buffer ends mid-record or mid-header — the caller should
+    /// `push` more bytes and try again — or `Err` if the buffered data is malformed.
+    pub fn next_record(&mut self) -> Result<Option<Record<'_>>, ParsingError<'_>> {
+        // Nothing currently borrows `buffer` (the Record from the last call was already
+        // dropped by the caller, since producing it borrowed `self` mutably), so it's safe
+        // to drop the previously-consumed prefix now.
+        if self.consumed > 0 {
+            self.buffer.drain(..self.consumed);
+            self.consumed = 0;
+        }
+
+        if self.metadata.is_none() {
+            let Some(len) = header_len(&self.buffer) else {
+                return Ok(None);
+            };
+
+            match parse_header(&self.buffer) {
+                Ok((_, metadata)) => self.metadata = Some(metadata.to_string()),
+                Err(nom::Err::Incomplete(_)) => return Ok(None),
+                // Rebuilt rather than returned as-is: `e` borrows `self.buffer`, and the
+                // elided lifetime on this method's return type ties that borrow to the same
+                // `&mut self` the `self.buffer.drain` below needs - see `rehome_header_error`.
+                Err(nom::Err::Error(e) | nom::Err::Failure(e)) => return Err(rehome_header_error(e)),
+            }
+
+            self.buffer.drain(..len);
+        }
+
+        let Some(len) = record_len(&self.buffer) else {
+            return Ok(None);
+        };
+
+        match parse_record(&self.buffer[..len]) {
+            Ok((rest, record)) => {
+                self.consumed = len - rest.len();
+                Ok(Some(record))
+            }
+            Err(nom::Err::Incomplete(_)) => Ok(None),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RecordPayload;
+
+    const HEADER: [u8; 0x10] = [
+        0x57, 0x50, 0x49, 0x4C, 0x4F, 0x47, // Magic = "WPILOG"
+        0x00, 0x01, // Version = 0x0100
+        0x04, 0x00, 0x00, 0x00, // Metadata Length = 4
+        0x74, 0x65, 0x73, 0x74, // Metadata = "test"
+    ];
+
+    const INT_RECORD: [u8; 0x0E] = [
+        0x20, // Timestamp Length = 3, Payload Size Length = 1, Entry Id Length = 1
+        0x01, // Entry Id = 1
+        0x08, // Payload Size = 8
+        0x40, 0x42, 0x0F, // Timestamp = 1_000_000
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Payload = [u8; 8]
+    ];
+
+    #[test]
+    fn test_next_record_non_degenerate_payload() {
+        // `INT_RECORD`'s payload is 8 bytes long, i.e. `size_len` (1) != `payload_size` (8) -
+        // exactly the case `record_len` previously panicked on.
+        let mut reader = DataLogReader::new();
+        reader.push(&HEADER);
+        reader.push(&INT_RECORD);
+
+        let record = reader
+            .next_record()
+            .expect("should parse")
+            .expect("should be fully buffered");
+
+        assert_eq!(record.entry_id, 1);
+        assert_eq!(record.timestamp, 1_000_000);
+        assert_eq!(record.payload, RecordPayload::Value(&3i64.to_le_bytes()));
+    }
+
+    #[test]
+    fn test_next_record_across_chunks() {
+        // Feeding the record one byte at a time should yield `Ok(None)` until it's whole.
+        let mut reader = DataLogReader::new();
+        reader.push(&HEADER);
+
+        for &byte in &INT_RECORD[..INT_RECORD.len() - 1] {
+            reader.push(&[byte]);
+            assert_eq!(reader.next_record().expect("should parse"), None);
+        }
+
+        reader.push(&INT_RECORD[INT_RECORD.len() - 1..]);
+        assert!(reader.next_record().expect("should parse").is_some());
+    }
+
+    #[test]
+    fn test_next_record_reports_invalid_magic() {
+        // Also exercises that an errored header parse doesn't leave a stray immutable borrow
+        // of `self.buffer` behind to conflict with the `self.buffer.drain` a successful parse
+        // would need later.
+        let mut bytes = b"BADMAG".to_vec(); // wrong magic, still 6 bytes
+        bytes.extend_from_slice(&0x0100u16.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // empty metadata string
+
+        let mut reader = DataLogReader::new();
+        reader.push(&bytes);
+
+        assert!(matches!(
+            reader.next_record(),
+            Err(ParsingError::InvalidMagic)
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! A structured view of each entry's lifetime, reconstructed from the flat `Vec<Record>`.
+//!
+//! WPILOG recycles a numeric entry id after a `Finish`, so a flat `entry_id -> name` map (as
+//! used elsewhere in this crate) can't tell two unrelated uses of the same id apart. [`Timeline`]
+//! instead keys on *generations*: a fresh `Start` for an already-seen id begins a new
+//! [`EntryGeneration`] rather than mutating the old one, and every `Metadata`/`Value` record is
+//! attributed to whichever generation of its id was open at that point in the log.
+
+use std::collections::HashMap;
+
+use crate::{DataLog, EntryType, RecordPayload};
+
+/// One generation of an entry id's life: from the `Start` that opened it up to (but not
+/// including) the `Finish` that closed it, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryGeneration<'a> {
+    pub entry_id: u32,
+    pub name: &'a str,
+    pub ty: EntryType<'a>,
+    /// `(timestamp, metadata)` for the `Start` and every subsequent `Metadata` record, in order.
+    pub metadata_history: Vec<(u64, &'a str)>,
+    pub start: u64,
+    pub finish: Option<u64>,
+    values: Vec<(u64, &'a [u8])>,
+}
+
+impl<'a> EntryGeneration<'a> {
+    /// Whether this generation has not yet seen a `Finish` record.
+    pub fn is_open(&self) -> bool {
+        self.finish.is_none()
+    }
+}
+
+/// A `Value` or `Finish` record whose entry id had no open generation when it was encountered —
+/// e.g. a corrupt log, or one truncated mid-entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrphanedRecord<'a> {
+    Value {
+        entry_id: u32,
+        timestamp: u64,
+        payload: &'a [u8],
+    },
+    Finish {
+        entry_id: u32,
+        timestamp: u64,
+    },
+}
+
+/// The reconstructed lifetime of every entry in a [`DataLog`].
+#[derive(Debug, Clone, Default)]
+pub struct Timeline<'a> {
+    generations: Vec<EntryGeneration<'a>>,
+    /// `Value` and `Finish` records that couldn't be attributed to any open generation,
+    /// reported rather than silently dropped. A `Metadata` record with no open generation is
+    /// dropped.
+    pub orphans: Vec<OrphanedRecord<'a>>,
+}
+
+impl<'a> Timeline<'a> {
+    /// Replays `log`'s records in order, reconstructing each entry id's generations.
+    pub fn build(log: &DataLog<'a>) -> Self {
+        let mut generations: Vec<EntryGeneration<'a>> = Vec::new();
+        // The currently-open generation for each entry id, as an index into `generations`.
+        let mut open: HashMap<u32, usize> = HashMap::new();
+        let mut orphans = Vec::new();
+
+        for record in &log.records {
+            match &record.payload {
+                RecordPayload::Start {
+                    target_entry_id,
+                    name,
+                    ty,
+                    metadata,
+                } => {
+                    let idx = generations.len();
+                    generations.push(EntryGeneration {
+                        entry_id: *target_entry_id,
+                        name,
+                        ty: ty.clone(),
+                        metadata_history: vec![(record.timestamp, metadata)],
+                        start: record.timestamp,
+                        finish: None,
+                        values: vec![],
+                    });
+                    open.insert(*target_entry_id, idx);
+                }
+                RecordPayload::Finish { target_entry_id } => match open.remove(target_entry_id) {
+                    Some(idx) => generations[idx].finish = Some(record.timestamp),
+                    None => orphans.push(OrphanedRecord::Finish {
+                        entry_id: *target_entry_id,
+                        timestamp: record.timestamp,
+                    }),
+                },
+                RecordPayload::Metadata {
+                    target_entry_id,
+                    metadata,
+                } => {
+                    if let Some(&idx) = open.get(target_entry_id) {
+                        generations[idx]
+                            .metadata_history
+                            .push((record.timestamp, metadata));
+                    }
+                }
+                RecordPayload::Value(payload) => match open.get(&record.entry_id) {
+                    Some(&idx) => generations[idx].values.push((record.timestamp, payload)),
+                    None => orphans.push(OrphanedRecord::Value {
+                        entry_id: record.entry_id,
+                        timestamp: record.timestamp,
+                        payload,
+                    }),
+                },
+            }
+        }
+
+        Self {
+            generations,
+            orphans,
+        }
+    }
+
+    /// Every generation (across every entry id) that was ever declared with the given name.
+    pub fn entries_named<'b>(
+        &'b self,
+        name: &'b str,
+    ) -> impl Iterator<Item = &'b EntryGeneration<'a>> {
+        self.generations.iter().filter(move |g| g.name == name)
+    }
+
+    /// The `(timestamp, payload)` pairs recorded against a single entry generation.
+    pub fn value_records_for<'b>(&self, generation: &'b EntryGeneration<'a>) -> &'b [(u64, &'a [u8])] {
+        &generation.values
+    }
+}
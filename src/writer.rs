@@ -0,0 +1,205 @@
+//! A WPILOG encoder, the write-side counterpart to [`crate::read_only`].
+//!
+//! `WPILogWriter` mirrors the record shape `read_only::WPILog::parse` decodes: it picks the
+//! minimal byte-length for each of a record's three variable-length fields and packs them into
+//! the same leading bitfield, so a parsed-then-rewritten log round-trips.
+
+use std::io::{self, Write};
+
+use crate::errors::WPILogParseError;
+
+/// Identifies an entry started with [`WPILogWriter::start_entry`].
+pub type EntryId = u32;
+
+/// The minimal number of little-endian bytes needed to represent `value`.
+fn min_bytes_u32(value: u32) -> u8 {
+    match value {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x1_0000..=0xFF_FFFF => 3,
+        _ => 4,
+    }
+}
+
+fn min_bytes_u64(value: u64) -> u8 {
+    match value {
+        0..=0xFFFF_FFFF => min_bytes_u32(value as u32),
+        0x1_0000_0000..=0xFF_FFFF_FFFF => 5,
+        0x100_0000_0000..=0xFFFF_FFFF_FFFF => 6,
+        0x1_0000_0000_0000..=0xFF_FFFF_FFFF_FFFF => 7,
+        _ => 8,
+    }
+}
+
+fn write_variable_u32<W: Write>(w: &mut W, value: u32, len: u8) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes()[..len as usize])
+}
+
+fn write_variable_u64<W: Write>(w: &mut W, value: u64, len: u8) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes()[..len as usize])
+}
+
+fn write_len_prefixed_string<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    w.write_all(&(s.len() as u32).to_le_bytes())?;
+    w.write_all(s.as_bytes())
+}
+
+/// Writes length-prefixed entry-id/payload-size/timestamp fields and the record's leading
+/// bitfield for a record whose payload is exactly `payload_len` bytes.
+fn write_record_header<W: Write>(
+    w: &mut W,
+    entry_id: EntryId,
+    payload_len: usize,
+    timestamp: u64,
+) -> io::Result<()> {
+    let id_len = min_bytes_u32(entry_id);
+    let size_len = min_bytes_u32(payload_len as u32);
+    let ts_len = min_bytes_u64(timestamp);
+
+    let lengths = (id_len - 1) | ((size_len - 1) << 2) | ((ts_len - 1) << 4);
+    w.write_all(&[lengths])?;
+
+    write_variable_u32(w, entry_id, id_len)?;
+    write_variable_u32(w, payload_len as u32, size_len)?;
+    write_variable_u64(w, timestamp, ts_len)
+}
+
+/// An incremental encoder for the `.wpilog` format.
+///
+/// Entries are started with [`start_entry`](Self::start_entry), which hands back an
+/// [`EntryId`] to pass to [`append`](Self::append), [`set_metadata`](Self::set_metadata), and
+/// [`finish_entry`](Self::finish_entry).
+pub struct WPILogWriter<W: Write> {
+    inner: W,
+    next_entry_id: EntryId,
+}
+
+impl<W: Write> WPILogWriter<W> {
+    /// Writes the magic, version, and extra-header string, then returns a writer ready to
+    /// accept entries.
+    pub fn new(mut inner: W, extra_header: &str) -> Result<Self, WPILogParseError> {
+        inner
+            .write_all(crate::WPILOG_MAGIC)
+            .and_then(|_| inner.write_all(&crate::SUPPORTED_VERSION.to_le_bytes()))
+            .and_then(|_| write_len_prefixed_string(&mut inner, extra_header))
+            .map_err(|e| WPILogParseError::Io(e.kind()))?;
+
+        Ok(Self {
+            inner,
+            // Entry id 0 is reserved for control records.
+            next_entry_id: 1,
+        })
+    }
+
+    fn write_control_record(
+        &mut self,
+        timestamp: u64,
+        control_type: u8,
+        target_entry_id: EntryId,
+        body: &[u8],
+    ) -> Result<(), WPILogParseError> {
+        let payload_len = 1 + 4 + body.len();
+
+        write_record_header(&mut self.inner, 0, payload_len, timestamp)
+            .and_then(|_| self.inner.write_all(&[control_type]))
+            .and_then(|_| self.inner.write_all(&target_entry_id.to_le_bytes()))
+            .and_then(|_| self.inner.write_all(body))
+            .map_err(|e| WPILogParseError::Io(e.kind()))
+    }
+
+    /// Declares a new entry, emitting a `Start` control record, and returns the id future
+    /// `append`/`set_metadata`/`finish_entry` calls should target.
+    pub fn start_entry(
+        &mut self,
+        timestamp: u64,
+        name: &str,
+        ty: &str,
+        metadata: &str,
+    ) -> Result<EntryId, WPILogParseError> {
+        let entry_id = self.next_entry_id;
+        self.next_entry_id += 1;
+
+        let mut body = vec![];
+        write_len_prefixed_string(&mut body, name).unwrap();
+        write_len_prefixed_string(&mut body, ty).unwrap();
+        write_len_prefixed_string(&mut body, metadata).unwrap();
+
+        self.write_control_record(timestamp, 0, entry_id, &body)?;
+
+        Ok(entry_id)
+    }
+
+    /// Emits a `Finish` control record for `entry_id`.
+    ///
+    /// WPILOG allows a finished entry id to be reused, but `start_entry` never does so: it
+    /// always hands out the next unused id.
+    pub fn finish_entry(&mut self, timestamp: u64, entry_id: EntryId) -> Result<(), WPILogParseError> {
+        self.write_control_record(timestamp, 1, entry_id, &[])
+    }
+
+    /// Emits a `SetMetadata` control record updating `entry_id`'s metadata string.
+    pub fn set_metadata(
+        &mut self,
+        timestamp: u64,
+        entry_id: EntryId,
+        metadata: &str,
+    ) -> Result<(), WPILogParseError> {
+        let mut body = vec![];
+        write_len_prefixed_string(&mut body, metadata).unwrap();
+
+        self.write_control_record(timestamp, 2, entry_id, &body)
+    }
+
+    /// Appends a raw value payload for `entry_id` at `timestamp`.
+    pub fn append(
+        &mut self,
+        timestamp: u64,
+        entry_id: EntryId,
+        payload: &[u8],
+    ) -> Result<(), WPILogParseError> {
+        write_record_header(&mut self.inner, entry_id, payload.len(), timestamp)
+            .and_then(|_| self.inner.write_all(payload))
+            .map_err(|e| WPILogParseError::Io(e.kind()))
+    }
+
+    /// Returns the underlying writer. It is not flushed first.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_only::WPILog;
+
+    #[test]
+    fn test_round_trip_through_read_only_parse() {
+        let mut writer = WPILogWriter::new(vec![], "test").expect("should write header");
+
+        let counter_id = writer
+            .start_entry(1_000_000, "/Counter", "int64", "")
+            .expect("should write Start");
+        writer
+            .append(1_000_000, counter_id, &3i64.to_le_bytes())
+            .expect("should write Value");
+        writer
+            .set_metadata(1_000_500, counter_id, "json")
+            .expect("should write SetMetadata");
+        writer
+            .append(2_000_000, counter_id, &7i64.to_le_bytes())
+            .expect("should write Value");
+        writer
+            .finish_entry(3_000_000, counter_id)
+            .expect("should write Finish");
+
+        let bytes = writer.into_inner();
+        let log = WPILog::parse(&bytes).expect("round-tripped log should parse");
+
+        let entry = log.get("/Counter").expect("entry should exist");
+        assert_eq!(entry.ty(), "int64");
+        assert_eq!(entry.get_value(0), Some(&(1_000_000, &3i64.to_le_bytes()[..])));
+        assert_eq!(entry.get_value(1), Some(&(2_000_000, &7i64.to_le_bytes()[..])));
+        assert_eq!(entry.finished_at(), Some(3_000_000));
+    }
+}